@@ -6,13 +6,31 @@ use crate::util::SqlExtension;
 pub enum AlterColumnAction {
     SetType {
         typ: Type,
+        /// Whether the column stays nullable across this change. MySQL's `MODIFY COLUMN`
+        /// restates the whole column definition, so it needs this even though Postgres only
+        /// looks at `typ` (SQLite rejects type changes altogether).
+        nullable: bool,
         using: Option<String>,
     },
-    SetNullable(bool),
+    SetNullable {
+        nullable: bool,
+        /// The column's (unchanged) type, needed to restate the full definition for MySQL's
+        /// `MODIFY COLUMN`.
+        typ: Type,
+    },
     AddConstraint {
         name: String,
         constraint: Constraint,
     },
+    DropConstraint {
+        name: String,
+    },
+    /// `None` clears the comment (`IS NULL`). Rendered only for Postgres, as
+    /// `COMMENT ON COLUMN`; MySQL and SQLite column comments are not supported.
+    SetComment(Option<String>),
+    /// `Some(expr)` sets the column's default to that (already-rendered) SQL expression;
+    /// `None` drops the default.
+    SetDefault(Option<String>),
 }
 
 /// Alter table action
@@ -25,21 +43,32 @@ pub enum AlterAction {
         name: String,
         action: AlterColumnAction,
     },
+    DropColumn {
+        name: String,
+        if_exists: bool,
+    },
+    /// Table-level comment. `None` clears the comment.
+    SetComment(Option<String>),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
 }
 
 impl AlterAction {
-    pub fn set_nullable(name: String, nullable: bool) -> Self {
+    pub fn set_nullable(name: String, nullable: bool, typ: Type) -> Self {
         Self::AlterColumn {
             name,
-            action: AlterColumnAction::SetNullable(nullable),
+            action: AlterColumnAction::SetNullable { nullable, typ },
         }
     }
 
-    pub fn set_type(name: String, typ: Type) -> Self {
+    pub fn set_type(name: String, typ: Type, nullable: bool) -> Self {
         Self::AlterColumn {
             name,
             action: AlterColumnAction::SetType {
                 typ,
+                nullable,
                 using: None,
             },
         }
@@ -55,6 +84,364 @@ impl AlterAction {
             },
         }
     }
+
+    pub fn drop_column(name: String, if_exists: bool) -> Self {
+        Self::DropColumn { name, if_exists }
+    }
+
+    pub fn drop_constraint(name: String, constraint_name: String) -> Self {
+        Self::AlterColumn {
+            name,
+            action: AlterColumnAction::DropConstraint { name: constraint_name },
+        }
+    }
+
+    pub fn rename_column(from: String, to: String) -> Self {
+        Self::RenameColumn { from, to }
+    }
+
+    pub fn set_default(name: String, default: Option<String>) -> Self {
+        Self::AlterColumn {
+            name,
+            action: AlterColumnAction::SetDefault(default),
+        }
+    }
+
+    /// Produce the inverse of this action, i.e. the action that would undo it.
+    ///
+    /// `previous` is the column as it existed before this action was applied, which is
+    /// needed to recover the prior type/nullability for actions that don't carry enough
+    /// information on their own. Returns `None` when the action cannot be reliably
+    /// inverted (e.g. a type change with a custom `USING` clause), so callers know the
+    /// rollback is irreversible rather than emitting incorrect SQL.
+    pub fn invert(&self, previous: &Column) -> Option<Self> {
+        match self {
+            Self::AddColumn { column } => Some(Self::DropColumn {
+                name: column.name.clone(),
+                if_exists: false,
+            }),
+            Self::AlterColumn { name, action } => {
+                let inverted = match action {
+                    AlterColumnAction::SetNullable { nullable, typ } => {
+                        AlterColumnAction::SetNullable {
+                            nullable: !nullable,
+                            typ: typ.clone(),
+                        }
+                    }
+                    AlterColumnAction::SetType { using, nullable, .. } => {
+                        if using.is_some() {
+                            return None;
+                        }
+                        AlterColumnAction::SetType {
+                            typ: previous.typ.clone(),
+                            nullable: *nullable,
+                            using: None,
+                        }
+                    }
+                    AlterColumnAction::AddConstraint { name, .. } => {
+                        AlterColumnAction::DropConstraint { name: name.clone() }
+                    }
+                    AlterColumnAction::DropConstraint { .. } => return None,
+                    AlterColumnAction::SetComment(_) => {
+                        AlterColumnAction::SetComment(previous.comment.clone())
+                    }
+                    AlterColumnAction::SetDefault(_) => {
+                        AlterColumnAction::SetDefault(previous.default.clone())
+                    }
+                };
+                Some(Self::AlterColumn {
+                    name: name.clone(),
+                    action: inverted,
+                })
+            }
+            Self::DropColumn { .. } => Some(Self::AddColumn {
+                column: previous.clone(),
+            }),
+            Self::SetComment(_) => None,
+            Self::RenameColumn { from, to } => Some(Self::RenameColumn {
+                from: to.clone(),
+                to: from.clone(),
+            }),
+        }
+    }
+
+    /// Whether `to_sql(dialect)` can render this action at all. `ToSql` has no fallible
+    /// return, so the handful of actions a dialect can't express (SQLite type/nullability/
+    /// default changes, MySQL and SQLite column comments) panic deep inside `write_sql`
+    /// instead of returning an error; callers
+    /// that assemble actions from a diff rather than writing them by hand should check this
+    /// first and skip/report the unsupported ones rather than risk the panic.
+    pub fn is_supported(&self, dialect: Dialect) -> bool {
+        match self {
+            Self::AlterColumn {
+                action:
+                    AlterColumnAction::SetType { .. }
+                    | AlterColumnAction::SetNullable { .. }
+                    | AlterColumnAction::SetDefault(_),
+                ..
+            } => !matches!(dialect, Dialect::Sqlite),
+            // MySQL column comments require restating the full column via `MODIFY COLUMN`,
+            // which isn't implemented yet; SQLite has no column comments at all.
+            Self::AlterColumn { action: AlterColumnAction::SetComment(_), .. } => {
+                matches!(dialect, Dialect::Postgres)
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether this action is rendered as a sub-clause of the enclosing `ALTER TABLE`
+    /// statement, as opposed to its own standalone statement (e.g. Postgres' `COMMENT ON`,
+    /// which needs the table name that only `AlterTable` has).
+    fn is_clause(&self, dialect: Dialect) -> bool {
+        match self {
+            Self::SetComment(_) => matches!(dialect, Dialect::MySql),
+            Self::AlterColumn { action: AlterColumnAction::SetComment(_), .. } => false,
+            _ => true,
+        }
+    }
+
+    /// Render a comment action as its own standalone statement, given the table it belongs
+    /// to. Only called by `AlterTable::write_sql` for actions where `is_clause` is `false`.
+    fn write_comment_sql(&self, buf: &mut String, dialect: Dialect, schema: &Option<String>, table: &str) {
+        match self {
+            Self::SetComment(comment) => {
+                buf.push_str("COMMENT ON TABLE ");
+                buf.push_table_name(schema, table);
+                buf.push_str(" IS ");
+                push_comment_literal(buf, comment);
+            }
+            Self::AlterColumn { name, action: AlterColumnAction::SetComment(comment) } => {
+                match dialect {
+                    Dialect::MySql => panic!(
+                        "MySQL column comments require restating the full column via MODIFY COLUMN, \
+                         which needs the column's type; not yet supported for column {name:?}"
+                    ),
+                    Dialect::Sqlite => panic!("SQLite does not support column comments"),
+                    _ => {
+                        buf.push_str("COMMENT ON COLUMN ");
+                        buf.push_table_name(schema, table);
+                        buf.push('.');
+                        buf.push_quoted(name);
+                        buf.push_str(" IS ");
+                        push_comment_literal(buf, comment);
+                    }
+                }
+            }
+            _ => unreachable!("write_comment_sql is only called for comment actions"),
+        }
+    }
+}
+
+fn push_comment_literal(buf: &mut String, comment: &Option<String>) {
+    match comment {
+        Some(text) => {
+            buf.push('\'');
+            buf.push_str(&text.replace('\'', "''"));
+            buf.push('\'');
+        }
+        None => buf.push_str("NULL"),
+    }
+}
+
+/// Tuning knobs for the rename-detection heuristic in [`detect_renames`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenameDetection {
+    /// Set to `false` to disable heuristic detection entirely and always emit drop+add for
+    /// columns that disappear/appear, for users who prefer that to be explicit.
+    pub enabled: bool,
+    /// Column name edit distance at or below which a name similarity counts as weak
+    /// supporting evidence for a rename.
+    pub max_name_distance: usize,
+}
+
+impl Default for RenameDetection {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_name_distance: 3,
+        }
+    }
+}
+
+/// Pair up columns that disappeared from the old schema with columns that appeared in the
+/// new one, scoring each candidate pair by how likely it is to be a rename rather than an
+/// unrelated drop+add: identical type is required, matching nullability is supporting
+/// evidence, and a small name edit distance is weak evidence. Only unambiguous best matches
+/// are returned, so callers can emit `AlterAction::RenameColumn` for them instead of a
+/// destructive drop+add pair; any column left unpaired should still be diffed normally.
+pub fn detect_renames(removed: &[Column], added: &[Column], config: RenameDetection) -> Vec<(String, String)> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    struct Candidate {
+        removed_idx: usize,
+        added_idx: usize,
+        score: u32,
+    }
+
+    let mut candidates = Vec::new();
+    for (removed_idx, r) in removed.iter().enumerate() {
+        for (added_idx, a) in added.iter().enumerate() {
+            if r.typ != a.typ {
+                // A rename always preserves the column's type; otherwise treat it as an
+                // unrelated drop+add rather than guessing.
+                continue;
+            }
+            let mut score = 10; // identical type: strong match
+            if r.nullable == a.nullable {
+                score += 3; // supporting evidence
+            }
+            let distance = levenshtein(&r.name, &a.name);
+            if distance <= config.max_name_distance {
+                score += (config.max_name_distance + 1 - distance) as u32; // weak match
+            }
+            candidates.push(Candidate { removed_idx, added_idx, score });
+        }
+    }
+
+    let mut renames = Vec::new();
+    let mut used_added = vec![false; added.len()];
+    for removed_idx in 0..removed.len() {
+        let own_candidates: Vec<&Candidate> = candidates
+            .iter()
+            .filter(|c| c.removed_idx == removed_idx && !used_added[c.added_idx])
+            .collect();
+        let Some(&best) = own_candidates.iter().max_by_key(|c| c.score) else {
+            continue;
+        };
+        let tied = own_candidates.iter().filter(|c| c.score == best.score).count() > 1;
+        if tied {
+            continue;
+        }
+        // Guard against false positives: the candidate added column must not be an equally
+        // good or better match for some other removed column.
+        let is_unique_best_for_added = candidates
+            .iter()
+            .filter(|c| c.added_idx == best.added_idx && c.removed_idx != removed_idx)
+            .all(|c| c.score < best.score);
+        if is_unique_best_for_added {
+            renames.push((
+                removed[best.removed_idx].name.clone(),
+                added[best.added_idx].name.clone(),
+            ));
+            used_added[best.added_idx] = true;
+        }
+    }
+    renames
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=b.len() {
+        dp[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1).min(dp[i][j - 1] + 1).min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[a.len()][b.len()]
+}
+
+/// Diff two versions of the same column (matched by identity, e.g. via [`detect_renames`] or
+/// because the name is unchanged) into the `AlterAction::AlterColumn`s needed to go from `old` to
+/// `new`. Currently this detects comment and default drift; type/nullability changes are
+/// still constructed directly by callers via `AlterAction::set_type`/`set_nullable`.
+///
+/// An action this `dialect` can't express (e.g. a MySQL column comment, or any SQLite
+/// default change) is left out of the result and explained in the returned `Vec<String>`
+/// instead, so callers can surface it rather than risk the panic in `ToSql::write_sql`.
+pub fn diff_columns(old: &Column, new: &Column, dialect: Dialect) -> (Vec<AlterAction>, Vec<String>) {
+    let mut actions = Vec::new();
+    let mut skipped = Vec::new();
+
+    if old.comment != new.comment {
+        let action = AlterAction::AlterColumn {
+            name: new.name.clone(),
+            action: AlterColumnAction::SetComment(new.comment.clone()),
+        };
+        if action.is_supported(dialect) {
+            actions.push(action);
+        } else {
+            skipped.push(format!(
+                "{dialect:?} cannot express the comment change on column {:?}",
+                new.name
+            ));
+        }
+    }
+
+    if old.default != new.default {
+        let action = AlterAction::set_default(new.name.clone(), new.default.clone());
+        if action.is_supported(dialect) {
+            actions.push(action);
+        } else {
+            skipped.push(format!(
+                "{dialect:?} cannot express the default change on column {:?}",
+                new.name
+            ));
+        }
+    }
+
+    (actions, skipped)
+}
+
+/// Diff two versions of a table's full column set into the `AlterAction`s needed to go from
+/// `old` to `new`: columns that disappeared and reappeared are first run through
+/// [`detect_renames`] and emitted as `RenameColumn` rather than a destructive drop+add, any
+/// column left over is a plain drop or add, and every column present on both sides (directly,
+/// or via a detected rename) is passed to [`diff_columns`] to catch other drift (comments,
+/// ...). See `diff_columns` for how actions this `dialect` can't express are reported instead
+/// of risking the `ToSql::write_sql` panic.
+pub fn diff_column_sets(
+    old: &[Column],
+    new: &[Column],
+    dialect: Dialect,
+    rename_config: RenameDetection,
+) -> (Vec<AlterAction>, Vec<String>) {
+    let mut actions = Vec::new();
+    let mut skipped = Vec::new();
+
+    let removed: Vec<Column> = old.iter().filter(|c| !new.iter().any(|n| n.name == c.name)).cloned().collect();
+    let added: Vec<Column> = new.iter().filter(|c| !old.iter().any(|o| o.name == c.name)).cloned().collect();
+
+    let renames = detect_renames(&removed, &added, rename_config);
+    for (from, to) in &renames {
+        actions.push(AlterAction::rename_column(from.clone(), to.clone()));
+    }
+
+    for column in &removed {
+        if !renames.iter().any(|(from, _)| from == &column.name) {
+            actions.push(AlterAction::drop_column(column.name.clone(), false));
+        }
+    }
+    for column in &added {
+        if !renames.iter().any(|(_, to)| to == &column.name) {
+            actions.push(AlterAction::AddColumn { column: column.clone() });
+        }
+    }
+
+    for old_column in old {
+        let new_name = renames
+            .iter()
+            .find(|(from, _)| from == &old_column.name)
+            .map(|(_, to)| to.as_str())
+            .unwrap_or(old_column.name.as_str());
+        let Some(new_column) = new.iter().find(|c| c.name == new_name) else {
+            continue;
+        };
+        let (pair_actions, pair_skipped) = diff_columns(old_column, new_column, dialect);
+        actions.extend(pair_actions);
+        skipped.extend(pair_skipped);
+    }
+
+    (actions, skipped)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -73,9 +460,23 @@ impl ToSql for AlterTable {
             if self.schema.is_some() { "." } else { "" },
             self.name
         ));
-        buf.push_str("ALTER TABLE ");
-        buf.push_table_name(&self.schema, &self.name);
-        buf.push_sql_sequence(&self.actions, ",", dialect);
+        let clauses: Vec<AlterAction> = self
+            .actions
+            .iter()
+            .filter(|action| action.is_clause(dialect))
+            .cloned()
+            .collect();
+        if !clauses.is_empty() {
+            buf.push_str("ALTER TABLE ");
+            buf.push_table_name(&self.schema, &self.name);
+            buf.push_sql_sequence(&clauses, ",", dialect);
+        }
+        for action in self.actions.iter().filter(|action| !action.is_clause(dialect)) {
+            if !buf.is_empty() {
+                buf.push_str("; ");
+            }
+            action.write_comment_sql(buf, dialect, &self.schema, &self.name);
+        }
     }
 }
 
@@ -89,10 +490,33 @@ impl ToSql for AlterAction {
             }
             AlterColumn { name, action } => {
                 use AlterColumnAction::*;
-                buf.push_str(" ALTER COLUMN ");
-                buf.push_quoted(name);
-                match action {
-                    SetType { typ, using } => {
+                match (dialect, action) {
+                    // MySQL has no standalone `ALTER COLUMN ... TYPE` / `SET NOT NULL` —
+                    // both are expressed by restating the whole column via `MODIFY COLUMN`.
+                    (Dialect::MySql, SetType { typ, nullable, .. })
+                    | (Dialect::MySql, SetNullable { typ, nullable }) => {
+                        buf.push_str(" MODIFY COLUMN ");
+                        buf.push_quoted(name);
+                        buf.push(' ');
+                        buf.push_sql(typ, dialect);
+                        if !*nullable {
+                            buf.push_str(" NOT NULL");
+                        }
+                    }
+                    // SQLite can't alter a column's type or nullability in place at all;
+                    // doing so requires the documented table-rebuild recipe (create a new
+                    // table, copy the rows across, drop the old table, rename the new one).
+                    (Dialect::Sqlite, SetType { .. }) | (Dialect::Sqlite, SetNullable { .. }) => {
+                        panic!(
+                            "SQLite does not support altering a column's type or nullability \
+                             (column {name:?}); rebuild the table instead (CREATE TABLE tmp, \
+                             INSERT INTO tmp SELECT ..., DROP TABLE <table>, \
+                             ALTER TABLE tmp RENAME TO <table>)"
+                        );
+                    }
+                    (_, SetType { typ, using, .. }) => {
+                        buf.push_str(" ALTER COLUMN ");
+                        buf.push_quoted(name);
                         buf.push_str(" TYPE ");
                         buf.push_sql(typ, dialect);
                         buf.push_str(" USING ");
@@ -104,20 +528,75 @@ impl ToSql for AlterAction {
                             buf.push_sql(typ, dialect);
                         }
                     }
-                    SetNullable(nullable) => {
+                    (_, SetNullable { nullable, .. }) => {
+                        buf.push_str(" ALTER COLUMN ");
+                        buf.push_quoted(name);
                         if *nullable {
                             buf.push_str(" DROP NOT NULL");
                         } else {
                             buf.push_str(" SET NOT NULL");
                         }
                     }
-                    AddConstraint { name, constraint } => {
-                        buf.push_str(" ADD CONSTRAINT ");
+                    (_, AddConstraint { name: constraint_name, constraint }) => {
+                        buf.push_str(" ALTER COLUMN ");
                         buf.push_quoted(name);
+                        buf.push_str(" ADD CONSTRAINT ");
+                        buf.push_quoted(constraint_name);
                         buf.push(' ');
                         buf.push_sql(constraint, dialect);
                     }
+                    // Unlike adding one, dropping a constraint is table-level in every
+                    // dialect: it's identified by name alone, not by the column it was
+                    // declared against.
+                    (_, DropConstraint { name: constraint_name }) => {
+                        buf.push_str(" DROP CONSTRAINT ");
+                        buf.push_quoted(constraint_name);
+                    }
+                    (_, SetComment(_)) => unreachable!(
+                        "column comments are never a clause; AlterTable::write_sql routes them \
+                         through write_comment_sql instead"
+                    ),
+                    // SQLite has no `ALTER COLUMN ... SET/DROP DEFAULT`; changing a default
+                    // requires the same table-rebuild recipe as a type change.
+                    (Dialect::Sqlite, SetDefault(_)) => {
+                        panic!(
+                            "SQLite does not support altering a column's default (column {name:?}); \
+                             rebuild the table instead (CREATE TABLE tmp, INSERT INTO tmp SELECT ..., \
+                             DROP TABLE <table>, ALTER TABLE tmp RENAME TO <table>)"
+                        );
+                    }
+                    (_, SetDefault(default)) => {
+                        buf.push_str(" ALTER COLUMN ");
+                        buf.push_quoted(name);
+                        match default {
+                            Some(expr) => {
+                                buf.push_str(" SET DEFAULT ");
+                                buf.push_str(expr);
+                            }
+                            None => buf.push_str(" DROP DEFAULT"),
+                        }
+                    }
+                }
+            }
+            DropColumn { name, if_exists } => {
+                buf.push_str(" DROP COLUMN ");
+                if *if_exists {
+                    buf.push_str("IF EXISTS ");
                 }
+                buf.push_quoted(name);
+            }
+            // Only reached on MySQL, where a table comment is an inline ALTER TABLE option;
+            // `AlterTable::write_sql` renders every other dialect as a standalone
+            // `COMMENT ON TABLE` via `write_comment_sql` instead.
+            SetComment(comment) => {
+                buf.push_str(" COMMENT = ");
+                push_comment_literal(buf, comment);
+            }
+            RenameColumn { from, to } => {
+                buf.push_str(" RENAME COLUMN ");
+                buf.push_quoted(from);
+                buf.push_str(" TO ");
+                buf.push_quoted(to);
             }
         }
     }
@@ -133,6 +612,7 @@ mod test {
             name: "foo".to_string(),
             action: AlterColumnAction::SetType {
                 typ: Type::Text,
+                nullable: true,
                 using: None,
             },
         };
@@ -145,6 +625,7 @@ mod test {
             name: "foo".to_string(),
             action: AlterColumnAction::SetType {
                 typ: Type::Text,
+                nullable: true,
                 using: Some("SUBSTRING(foo, 1, 3)".to_string()),
             },
         };
@@ -153,4 +634,345 @@ mod test {
             r#" ALTER COLUMN "foo" TYPE character varying USING SUBSTRING(foo, 1, 3)"#
         );
     }
+
+    #[test]
+    fn test_alter_action_mysql() {
+        let alter = AlterAction::set_type("foo".to_string(), Type::Text, false);
+        assert_eq!(
+            alter.to_sql(Dialect::MySql),
+            r#" MODIFY COLUMN "foo" character varying NOT NULL"#
+        );
+
+        let alter = AlterAction::set_nullable("foo".to_string(), true, Type::Text);
+        assert_eq!(
+            alter.to_sql(Dialect::MySql),
+            r#" MODIFY COLUMN "foo" character varying"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not support altering")]
+    fn test_alter_action_sqlite_set_type_panics() {
+        let alter = AlterAction::set_type("foo".to_string(), Type::Text, true);
+        alter.to_sql(Dialect::Sqlite);
+    }
+
+    #[test]
+    fn test_is_supported() {
+        let set_type = AlterAction::set_type("foo".to_string(), Type::Text, true);
+        assert!(set_type.is_supported(Dialect::Postgres));
+        assert!(set_type.is_supported(Dialect::MySql));
+        assert!(!set_type.is_supported(Dialect::Sqlite));
+
+        let set_nullable = AlterAction::set_nullable("foo".to_string(), true, Type::Text);
+        assert!(!set_nullable.is_supported(Dialect::Sqlite));
+
+        let drop_column = AlterAction::drop_column("foo".to_string(), false);
+        assert!(drop_column.is_supported(Dialect::Sqlite));
+    }
+
+    #[test]
+    fn test_invert() {
+        let previous = Column {
+            name: "foo".to_string(),
+            typ: Type::Integer,
+            nullable: true,
+            comment: None,
+            default: None,
+        };
+
+        let add = AlterAction::AddColumn { column: previous.clone() };
+        assert_eq!(
+            add.invert(&previous),
+            Some(AlterAction::DropColumn {
+                name: "foo".to_string(),
+                if_exists: false,
+            })
+        );
+
+        let set_nullable = AlterAction::set_nullable("foo".to_string(), false, Type::Integer);
+        assert_eq!(
+            set_nullable.invert(&previous),
+            Some(AlterAction::set_nullable("foo".to_string(), true, Type::Integer))
+        );
+
+        let set_type = AlterAction::set_type("foo".to_string(), Type::Text, true);
+        assert_eq!(
+            set_type.invert(&previous),
+            Some(AlterAction::set_type("foo".to_string(), Type::Integer, true))
+        );
+
+        let narrowing = AlterAction::AlterColumn {
+            name: "foo".to_string(),
+            action: AlterColumnAction::SetType {
+                typ: Type::Text,
+                nullable: true,
+                using: Some("SUBSTRING(foo, 1, 3)".to_string()),
+            },
+        };
+        assert_eq!(narrowing.invert(&previous), None);
+
+        let drop_column = AlterAction::drop_column("foo".to_string(), false);
+        assert_eq!(
+            drop_column.invert(&previous),
+            Some(AlterAction::AddColumn { column: previous.clone() })
+        );
+
+        let drop_constraint = AlterAction::drop_constraint("foo".to_string(), "fk_foo_bar".to_string());
+        assert_eq!(drop_constraint.invert(&previous), None);
+    }
+
+    #[test]
+    fn test_drop_actions() {
+        let drop_column = AlterAction::drop_column("foo".to_string(), true);
+        assert_eq!(drop_column.to_sql(Dialect::Postgres), r#" DROP COLUMN IF EXISTS "foo""#);
+
+        let drop_constraint = AlterAction::drop_constraint("foo".to_string(), "fk_foo_bar".to_string());
+        assert_eq!(
+            drop_constraint.to_sql(Dialect::Postgres),
+            r#" DROP CONSTRAINT "fk_foo_bar""#
+        );
+    }
+
+    #[test]
+    fn test_set_comment() {
+        let alter = AlterTable {
+            schema: None,
+            name: "users".to_string(),
+            actions: vec![AlterAction::SetComment(Some("a user".to_string()))],
+        };
+        assert_eq!(
+            alter.to_sql(Dialect::Postgres),
+            r#"COMMENT ON TABLE "users" IS 'a user'"#
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::MySql),
+            r#"ALTER TABLE "users" COMMENT = 'a user'"#
+        );
+
+        let clear = AlterTable {
+            schema: None,
+            name: "users".to_string(),
+            actions: vec![AlterAction::SetComment(None)],
+        };
+        assert_eq!(
+            clear.to_sql(Dialect::Postgres),
+            r#"COMMENT ON TABLE "users" IS NULL"#
+        );
+
+        let column_comment = AlterTable {
+            schema: None,
+            name: "users".to_string(),
+            actions: vec![AlterAction::AlterColumn {
+                name: "email".to_string(),
+                action: AlterColumnAction::SetComment(Some("o's address".to_string())),
+            }],
+        };
+        assert_eq!(
+            column_comment.to_sql(Dialect::Postgres),
+            r#"COMMENT ON COLUMN "users"."email" IS 'o''s address'"#
+        );
+    }
+
+    #[test]
+    fn test_diff_columns_comment() {
+        let old = Column {
+            name: "email".to_string(),
+            typ: Type::Text,
+            nullable: true,
+            comment: None,
+            default: None,
+        };
+        let new = Column {
+            comment: Some("o's address".to_string()),
+            ..old.clone()
+        };
+
+        let (actions, skipped) = diff_columns(&old, &new, Dialect::Postgres);
+        assert_eq!(
+            actions,
+            vec![AlterAction::AlterColumn {
+                name: "email".to_string(),
+                action: AlterColumnAction::SetComment(Some("o's address".to_string())),
+            }]
+        );
+        assert!(skipped.is_empty());
+
+        // MySQL can't express a column comment change yet; it's reported instead of panicking.
+        let (actions, skipped) = diff_columns(&old, &new, Dialect::MySql);
+        assert!(actions.is_empty());
+        assert_eq!(skipped.len(), 1);
+
+        // No drift, nothing to do.
+        let (actions, skipped) = diff_columns(&old, &old, Dialect::Postgres);
+        assert!(actions.is_empty());
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn test_rename_column() {
+        let rename = AlterAction::rename_column("foo".to_string(), "bar".to_string());
+        assert_eq!(
+            rename.to_sql(Dialect::Postgres),
+            r#" RENAME COLUMN "foo" TO "bar""#
+        );
+
+        let previous = Column {
+            name: "bar".to_string(),
+            typ: Type::Integer,
+            nullable: true,
+            comment: None,
+            default: None,
+        };
+        assert_eq!(
+            rename.invert(&previous),
+            Some(AlterAction::rename_column("bar".to_string(), "foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_detect_renames() {
+        let removed = vec![Column {
+            name: "frist_name".to_string(),
+            typ: Type::Text,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+        let added = vec![Column {
+            name: "first_name".to_string(),
+            typ: Type::Text,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+        assert_eq!(
+            detect_renames(&removed, &added, RenameDetection::default()),
+            vec![("frist_name".to_string(), "first_name".to_string())]
+        );
+
+        // A type change is not a rename, even if the name is identical.
+        let removed_typed = vec![Column {
+            name: "age".to_string(),
+            typ: Type::Integer,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+        let added_typed = vec![Column {
+            name: "age".to_string(),
+            typ: Type::Text,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+        assert!(detect_renames(&removed_typed, &added_typed, RenameDetection::default()).is_empty());
+
+        // Disabling the heuristic always yields no matches.
+        assert_eq!(
+            detect_renames(&removed, &added, RenameDetection { enabled: false, ..Default::default() }),
+            Vec::<(String, String)>::new()
+        );
+    }
+
+    #[test]
+    fn test_diff_column_sets_rename() {
+        let old = vec![Column {
+            name: "frist_name".to_string(),
+            typ: Type::Text,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+        let new = vec![Column {
+            name: "first_name".to_string(),
+            typ: Type::Text,
+            nullable: false,
+            comment: None,
+            default: None,
+        }];
+
+        let (actions, skipped) = diff_column_sets(&old, &new, Dialect::Postgres, RenameDetection::default());
+        assert_eq!(
+            actions,
+            vec![AlterAction::rename_column("frist_name".to_string(), "first_name".to_string())]
+        );
+        assert!(skipped.is_empty());
+
+        // With the heuristic disabled, the same pair is emitted as an explicit drop+add.
+        let (actions, _) = diff_column_sets(
+            &old,
+            &new,
+            Dialect::Postgres,
+            RenameDetection { enabled: false, ..Default::default() },
+        );
+        assert_eq!(
+            actions,
+            vec![
+                AlterAction::drop_column("frist_name".to_string(), false),
+                AlterAction::AddColumn { column: new[0].clone() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_default() {
+        let alter = AlterAction::set_default("foo".to_string(), Some("0".to_string()));
+        assert_eq!(
+            alter.to_sql(Dialect::Postgres),
+            r#" ALTER COLUMN "foo" SET DEFAULT 0"#
+        );
+
+        let drop = AlterAction::set_default("foo".to_string(), None);
+        assert_eq!(
+            drop.to_sql(Dialect::Postgres),
+            r#" ALTER COLUMN "foo" DROP DEFAULT"#
+        );
+
+        let previous = Column {
+            name: "foo".to_string(),
+            typ: Type::Integer,
+            nullable: true,
+            comment: None,
+            default: Some("0".to_string()),
+        };
+        assert_eq!(
+            alter.invert(&previous),
+            Some(AlterAction::set_default("foo".to_string(), Some("0".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_diff_columns_default() {
+        let old = Column {
+            name: "retries".to_string(),
+            typ: Type::Integer,
+            nullable: false,
+            comment: None,
+            default: None,
+        };
+        let new = Column {
+            default: Some("0".to_string()),
+            ..old.clone()
+        };
+
+        let (actions, skipped) = diff_columns(&old, &new, Dialect::Postgres);
+        assert_eq!(
+            actions,
+            vec![AlterAction::set_default("retries".to_string(), Some("0".to_string()))]
+        );
+        assert!(skipped.is_empty());
+
+        // SQLite can't express a default change in place; it's reported instead of panicking.
+        let (actions, skipped) = diff_columns(&old, &new, Dialect::Sqlite);
+        assert!(actions.is_empty());
+        assert_eq!(skipped.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not support altering a column's default")]
+    fn test_set_default_sqlite_panics() {
+        let alter = AlterAction::set_default("foo".to_string(), Some("0".to_string()));
+        alter.to_sql(Dialect::Sqlite);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use crate::Type;
+
+/// A single column in a table, as modeled for schema diffing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub typ: Type,
+    pub nullable: bool,
+    /// Emitted as `COMMENT ON COLUMN` (Postgres only; MySQL and SQLite column comments are
+    /// not supported). `None` means no comment is set.
+    pub comment: Option<String>,
+    /// The column's default value, already rendered as a SQL expression. `None` means no
+    /// default is set.
+    pub default: Option<String>,
+}